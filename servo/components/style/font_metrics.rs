@@ -11,17 +11,50 @@ use app_units::Au;
 use context::SharedStyleContext;
 use logical_geometry::WritingMode;
 use media_queries::Device;
+use properties::longhands::system_font::{ComputedSystemFont, SystemFont};
 use properties::style_structs::Font;
+use std::collections::HashMap;
 use std::fmt;
+use std::sync::RwLock;
+use values::computed::Length;
 
 /// Represents the font metrics that style needs from a font to compute the
-/// value of certain CSS units like `ex`.
+/// value of certain CSS units like `ex`, `ch`, `cap`, `ic`, and `lh`/`rlh`.
+///
+/// These are kept as `Length` rather than `Au`: unlike most of the style
+/// system, this is the one place that otherwise would have rounded to
+/// app units for no reason other than historical Gecko interop, which
+/// throws away fractional-pixel precision `ex`/`ch`/etc. resolution could
+/// otherwise keep.
+///
+/// Each field is `Option` because a given `FontMetricsProvider` may only be
+/// able to answer some of these for a particular font; callers fall back to
+/// an approximation from the font size when a metric comes back `None`.
 #[derive(Debug, PartialEq, Clone)]
 pub struct FontMetrics {
-    /// The x-height of the font.
-    pub x_height: Au,
-    /// The zero advance.
-    pub zero_advance_measure: Au,
+    /// The x-height of the font, used for the `ex` unit.
+    pub x_height: Option<Length>,
+    /// The advance of the zero glyph, used for the `ch` unit.
+    ///
+    /// This is usually writing-mode dependent (the vertical advance of '0'
+    /// differs from its horizontal one); which one a given `query` call
+    /// returned here depends on the `FontMetricsOrientation` it was asked
+    /// for.
+    pub zero_advance_measure: Option<Length>,
+    /// The cap-height of the font, used for the `cap` unit.
+    pub cap_height: Option<Length>,
+    /// The advance of the CJK water ideograph, U+6C34, used for the `ic`
+    /// unit.
+    pub ic_width: Option<Length>,
+    /// The font's ascent, used together with `descent` and `line_gap` for
+    /// the `lh`/`rlh` units.
+    pub ascent: Option<Length>,
+    /// The font's descent, used together with `ascent` and `line_gap` for
+    /// the `lh`/`rlh` units.
+    pub descent: Option<Length>,
+    /// The font's line gap, used together with `ascent` and `descent` for
+    /// the `lh`/`rlh` units.
+    pub line_gap: Option<Length>,
 }
 
 /// The result for querying font metrics for a given font family.
@@ -34,6 +67,20 @@ pub enum FontMetricsQueryResult {
     NotAvailable,
 }
 
+/// Which axis a `FontMetricsProvider::query` call wants its orientation-
+/// dependent metrics (currently just `zero_advance_measure`) resolved in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FontMetricsOrientation {
+    /// Resolve metrics in whatever axis the writing mode passed to `query`
+    /// calls for, e.g. the vertical advance of '0' for `ch` in vertical
+    /// text.
+    MatchContext,
+    /// Always resolve horizontal metrics, even if the writing mode is
+    /// vertical. Cheaper than `MatchContext` when a caller only ever needs
+    /// the horizontal value.
+    Horizontal,
+}
+
 /// A trait used to represent something capable of providing us font metrics.
 pub trait FontMetricsProvider: fmt::Debug {
     /// Obtain the metrics for given font family.
@@ -41,41 +88,169 @@ pub trait FontMetricsProvider: fmt::Debug {
     /// TODO: We could make this take the full list, I guess, and save a few
     /// virtual calls in the case we are repeatedly unable to find font metrics?
     /// That is not too common in practice though.
-    fn query(&self, _font: &Font, _font_size: Au, _wm: WritingMode,
-             _in_media_query: bool, _device: &Device) -> FontMetricsQueryResult {
+    ///
+    /// Providers that can't tell horizontal and vertical metrics apart are
+    /// free to ignore `orientation` and always answer with horizontal
+    /// metrics.
+    fn query(&self, _font: &Font, _font_size: Au, _orientation: FontMetricsOrientation,
+             _wm: WritingMode, _in_media_query: bool, _device: &Device) -> FontMetricsQueryResult {
         FontMetricsQueryResult::NotAvailable
     }
 
     /// Get default size of a given language and generic family
-    fn get_size(&self, font_name: &Atom, font_family: u8) -> Au;
+    ///
+    /// This is called repeatedly from parallel style workers during the
+    /// cascade, but platform base-size lookups backed by a preference
+    /// service (e.g. Gecko's `GetFontPrefsForLangHelper`) are main-thread
+    /// only and cannot safely be re-run on every call. Implementations
+    /// backed by such an API should resolve the answer once per
+    /// `(font_name, font_family)` pair and serve it out of a
+    /// `BaseSizeCache`, so that `get_size` itself is a read-locked map
+    /// lookup with no FFI call on the hot path.
+    fn get_size(&self, font_name: &Atom, font_family: u8) -> Length;
+
+    /// Resolve a CSS system-font keyword (`caption`, `menu`, etc.) to the
+    /// platform's default family/size/weight/style/stretch for it.
+    ///
+    /// Gecko answers this through `nsLookAndFeel`/`nsFont` directly (see
+    /// `Gecko_nsFont_InitSystem`), so it never calls this method. Backends
+    /// that haven't been wired up to a toolkit, or that are asked about a
+    /// keyword they don't know how to resolve, should return `None`; callers
+    /// fall back to a generic sans-serif family at the default medium size.
+    fn get_system_font(&self, _system: SystemFont) -> Option<ComputedSystemFont> {
+        None
+    }
 
     /// Construct from a shared style context
     fn create_from(context: &SharedStyleContext) -> Self where Self: Sized;
 }
 
+/// A concurrent cache of resolved base font sizes, keyed by `(language,
+/// generic_family)`.
+///
+/// A `FontMetricsProvider` whose `get_size` would otherwise need to reach
+/// into main-thread-only platform state can own one of these, populating it
+/// eagerly in `create_from` or lazily the first time a given key is
+/// requested, so that style worker threads only ever read an
+/// already-resolved snapshot.
+#[derive(Debug, Default)]
+pub struct BaseSizeCache {
+    sizes: RwLock<HashMap<(Atom, u8), Length>>,
+}
+
+impl BaseSizeCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        BaseSizeCache { sizes: RwLock::new(HashMap::new()) }
+    }
+
+    /// Returns the cached base size for `(language, generic_family)`, if it
+    /// has been resolved already.
+    pub fn get(&self, language: &Atom, generic_family: u8) -> Option<Length> {
+        self.sizes.read().unwrap().get(&(language.clone(), generic_family)).cloned()
+    }
+
+    /// Records the resolved base size for `(language, generic_family)`.
+    pub fn insert(&self, language: Atom, generic_family: u8, size: Length) {
+        self.sizes.write().unwrap().insert((language, generic_family), size);
+    }
+}
+
 #[derive(Debug)]
-/// Dummy font metrics provider, for use by Servo
-/// and in cases where gecko doesn't need font metrics
+/// Dummy font metrics provider, for use in cases where gecko doesn't need
+/// font metrics (e.g. outside of layout, or in tests).
 pub struct DummyProvider;
 
-#[cfg(feature = "servo")]
-/// Servo doesn't do font metrics yet, use same dummy provider.
-pub type ServoMetricsProvider = DummyProvider;
-
 impl FontMetricsProvider for DummyProvider {
     fn create_from(_: &SharedStyleContext) -> Self {
         DummyProvider
     }
 
-    fn get_size(&self, _font_name: &Atom, _font_family: u8) -> Au {
+    fn get_size(&self, _font_name: &Atom, _font_family: u8) -> Length {
         unreachable!("Dummy provider should never be used to compute font size")
     }
 }
 
-// Servo's font metrics provider will probably not live in this crate, so this will
-// have to be replaced with something else (perhaps a trait method on TElement)
-// when we get there
+/// Servo's real font-metrics provider, backed by its font cache thread.
+///
+/// Unlike `DummyProvider`, `query` actually selects the font face matching
+/// the requested `Font`/size/writing-mode and reads its x-height and the
+/// advance of the '0' glyph straight off of it, and `get_size` answers from
+/// Servo's per-generic-family default-size preferences -- cached in a
+/// `BaseSizeCache`, resolved once per `(font_name, font_family)` -- rather
+/// than panicking.
+#[cfg(feature = "servo")]
+#[derive(Debug)]
+pub struct ServoMetricsProvider {
+    font_cache_thread: ::gfx::font_cache_thread::FontCacheThread,
+    base_sizes: BaseSizeCache,
+}
+
+#[cfg(feature = "servo")]
+impl ServoMetricsProvider {
+    /// Creates a new provider holding its own handle to the current
+    /// font cache thread, for use where a `SharedStyleContext` isn't
+    /// available yet (mirrors `GeckoFontMetricsProvider::new()`).
+    pub fn new() -> Self {
+        ServoMetricsProvider {
+            font_cache_thread: ::gfx::font_cache_thread::FontCacheThread::current(),
+            base_sizes: BaseSizeCache::new(),
+        }
+    }
+}
+
+#[cfg(feature = "servo")]
+impl FontMetricsProvider for ServoMetricsProvider {
+    fn create_from(context: &SharedStyleContext) -> Self {
+        ServoMetricsProvider {
+            font_cache_thread: context.font_cache_thread.clone(),
+            base_sizes: BaseSizeCache::new(),
+        }
+    }
+
+    fn query(&self, font: &Font, font_size: Au, orientation: FontMetricsOrientation,
+             wm: WritingMode, _in_media_query: bool, _device: &Device) -> FontMetricsQueryResult {
+        let vertical = orientation == FontMetricsOrientation::MatchContext && wm.is_vertical();
+        match self.font_cache_thread.find_matching_font_metrics(font, font_size, vertical) {
+            Some(metrics) => FontMetricsQueryResult::Available(FontMetrics {
+                x_height: Some(metrics.x_height),
+                zero_advance_measure: Some(metrics.zero_advance_measure),
+                // Not read off the font face yet; callers fall back to a
+                // font-size-based approximation for these until Servo's
+                // font backend exposes them too.
+                cap_height: None,
+                ic_width: None,
+                ascent: None,
+                descent: None,
+                line_gap: None,
+            }),
+            None => FontMetricsQueryResult::NotAvailable,
+        }
+    }
+
+    fn get_size(&self, font_name: &Atom, font_family: u8) -> Length {
+        if let Some(size) = self.base_sizes.get(font_name, font_family) {
+            return size;
+        }
+        let size = self.font_cache_thread.default_font_size(font_name, font_family);
+        self.base_sizes.insert(font_name.clone(), font_family, size);
+        size
+    }
+}
+
+// Servo's font metrics provider will probably not live in this crate forever,
+// so this will have to be replaced with something else (perhaps a trait
+// method on TElement) when we get there.
 
+// `GeckoFontMetricsProvider` is the one place any of this still goes through
+// `Au`: it talks to `nsFontMetrics`/`nsStyleFont` over FFI, which hands back
+// app units, so it converts to `Length` right at that boundary before
+// storing into `FontMetrics`/`BaseSizeCache` or returning from `get_size`.
+// Everything on this side of the boundary, and everything that consumes
+// `FontMetrics`, works in `Length`. Its `query` also requests vertical
+// metrics from Gecko whenever `orientation` is `MatchContext` and the
+// writing mode is vertical, so `ch` in vertical text uses the vertical
+// advance of '0' rather than silently reusing the horizontal one.
 #[cfg(feature = "gecko")]
 /// Construct a font metrics provider for the current product
 pub fn get_metrics_provider_for_product() -> ::gecko::wrapper::GeckoFontMetricsProvider {
@@ -85,5 +260,5 @@ pub fn get_metrics_provider_for_product() -> ::gecko::wrapper::GeckoFontMetricsP
 #[cfg(feature = "servo")]
 /// Construct a font metrics provider for the current product
 pub fn get_metrics_provider_for_product() -> ServoMetricsProvider {
-    ServoMetricsProvider
+    ServoMetricsProvider::new()
 }
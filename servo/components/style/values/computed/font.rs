@@ -0,0 +1,60 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Computed types for font properties, factored out of the `font-size`
+//! longhand so other consumers (the `font` shorthand, presentation-attribute
+//! mapping) can share them without reaching into mako-generated internals.
+
+use app_units::Au;
+use std::fmt;
+use style_traits::ToCss;
+use values::specified::font::KeywordSize;
+
+/// Tracks which keyword (if any) a computed font-size derived from, so the
+/// size can be recomputed when the base size changes (zoom,
+/// `-moz-min-font-size-ratio`) without losing the keyword.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "servo", derive(HeapSizeOf))]
+pub struct KeywordInfo {
+    pub kw: KeywordSize,
+    pub factor: f32,
+    pub offset: Au,
+}
+
+impl KeywordInfo {
+    /// A bare keyword, not scaled or offset by anything.
+    pub fn new(kw: KeywordSize) -> Self {
+        KeywordInfo { kw: kw, factor: 1., offset: Au(0) }
+    }
+
+    /// Compose this info with a `factor` scale and an additional `offset`,
+    /// as done by `larger`/`smaller`, percentages, and calc().
+    pub fn compose(&self, factor: f32, offset: Au) -> Self {
+        KeywordInfo {
+            kw: self.kw,
+            factor: self.factor * factor,
+            offset: self.offset.scale_by(factor) + offset,
+        }
+    }
+}
+
+/// The computed value of `font-size`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "servo", derive(HeapSizeOf))]
+pub struct FontSize {
+    pub size: Au,
+    pub keyword_info: Option<KeywordInfo>,
+}
+
+impl FontSize {
+    pub fn new(size: Au, keyword_info: Option<KeywordInfo>) -> Self {
+        FontSize { size: size, keyword_info: keyword_info }
+    }
+}
+
+impl ToCss for FontSize {
+    fn to_css<W>(&self, dest: &mut W) -> fmt::Result where W: fmt::Write {
+        self.size.to_css(dest)
+    }
+}
@@ -0,0 +1,352 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Specified types for font properties, factored out of the `font-size`
+//! longhand so other consumers (the `font` shorthand, presentation-attribute
+//! mapping for HTML's `size=`) can reuse the keyword table and parsing
+//! without reaching into mako-generated internals.
+
+use app_units::Au;
+use cssparser::Parser;
+use parser::{Parse, ParserContext};
+use properties::longhands::system_font::{self, SystemFont};
+use std::fmt;
+use style_traits::ToCss;
+use values::FONT_MEDIUM_PX;
+use values::HasViewportPercentage;
+use values::computed::{Context, ToComputedValue};
+use values::computed::font::{FontSize as ComputedFontSize, KeywordInfo};
+use values::specified::{LengthOrPercentage, NoCalcLength, Percentage};
+
+/// CSS font keywords.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "servo", derive(HeapSizeOf))]
+pub enum KeywordSize {
+    XXSmall = 0,
+    XSmall = 1,
+    Small = 2,
+    Medium = 3,
+    Large = 4,
+    XLarge = 5,
+    XXLarge = 6,
+    // This is not a real font keyword and will not parse; HTML font-size 7
+    // corresponds to this value.
+    XXXLarge = 7,
+}
+
+pub use self::KeywordSize::*;
+
+impl KeywordSize {
+    pub fn parse(input: &mut Parser) -> Result<Self, ()> {
+        Ok(match_ignore_ascii_case! {&*input.expect_ident()?,
+            "xx-small" => XXSmall,
+            "x-small" => XSmall,
+            "small" => Small,
+            "medium" => Medium,
+            "large" => Large,
+            "x-large" => XLarge,
+            "xx-large" => XXLarge,
+            _ => return Err(())
+        })
+    }
+}
+
+impl Default for KeywordSize {
+    fn default() -> Self {
+        Medium
+    }
+}
+
+impl ToCss for KeywordSize {
+    fn to_css<W>(&self, dest: &mut W) -> fmt::Result where W: fmt::Write {
+        dest.write_str(match *self {
+            XXSmall => "xx-small",
+            XSmall => "x-small",
+            Small => "small",
+            Medium => "medium",
+            Large => "large",
+            XLarge => "x-large",
+            XXLarge => "xx-large",
+            XXXLarge => "",
+        })
+    }
+}
+
+#[cfg(feature = "servo")]
+impl ToComputedValue for KeywordSize {
+    type ComputedValue = Au;
+    #[inline]
+    fn to_computed_value(&self, _: &Context) -> Au {
+        // https://drafts.csswg.org/css-fonts-3/#font-size-prop
+        match *self {
+            XXSmall => Au::from_px(FONT_MEDIUM_PX) * 3 / 5,
+            XSmall => Au::from_px(FONT_MEDIUM_PX) * 3 / 4,
+            Small => Au::from_px(FONT_MEDIUM_PX) * 8 / 9,
+            Medium => Au::from_px(FONT_MEDIUM_PX),
+            Large => Au::from_px(FONT_MEDIUM_PX) * 6 / 5,
+            XLarge => Au::from_px(FONT_MEDIUM_PX) * 3 / 2,
+            XXLarge => Au::from_px(FONT_MEDIUM_PX) * 2,
+            XXXLarge => Au::from_px(FONT_MEDIUM_PX) * 3,
+        }
+    }
+
+    #[inline]
+    fn from_computed_value(_: &Au) -> Self {
+        unreachable!()
+    }
+}
+
+#[cfg(feature = "gecko")]
+impl ToComputedValue for KeywordSize {
+    type ComputedValue = Au;
+    #[inline]
+    fn to_computed_value(&self, cx: &Context) -> Au {
+        use gecko_bindings::bindings::Gecko_nsStyleFont_GetBaseSize;
+        use selectors::parser::QuirksMode;
+        use values::specified::length::au_to_int_px;
+        // Data from nsRuleNode.cpp in Gecko
+        // Mapping from base size and HTML size to pixels
+        // The first index is (base_size - 9), the second is the
+        // HTML size. "0" is CSS keyword xx-small, not HTML size 0,
+        // since HTML size 0 is the same as 1.
+        //
+        //  xxs   xs      s      m     l      xl     xxl   -
+        //  -     0/1     2      3     4      5      6     7
+        static FONT_SIZE_MAPPING: [[i32; 8]; 8] = [
+            [9,    9,     9,     9,    11,    14,    18,    27],
+            [9,    9,     9,    10,    12,    15,    20,    30],
+            [9,    9,    10,    11,    13,    17,    22,    33],
+            [9,    9,    10,    12,    14,    18,    24,    36],
+            [9,   10,    12,    13,    16,    20,    26,    39],
+            [9,   10,    12,    14,    17,    21,    28,    42],
+            [9,   10,    13,    15,    18,    23,    30,    45],
+            [9,   10,    13,    16,    18,    24,    32,    48]
+        ];
+
+        // In quirks mode, nsRuleNode shrinks the xx-small..small keywords
+        // relative to typical base sizes; everything else in the table is
+        // unchanged.
+        static FONT_SIZE_MAPPING_QUIRKS: [[i32; 8]; 8] = [
+            [9,    9,     9,     9,    11,    14,    18,    27],
+            [9,    9,     9,    10,    12,    15,    20,    30],
+            [9,    9,     9,    11,    13,    17,    22,    33],
+            [9,    9,     9,    12,    14,    18,    24,    36],
+            [9,    9,    10,    13,    16,    20,    26,    39],
+            [9,   10,    11,    14,    17,    21,    28,    42],
+            [9,   10,    11,    15,    18,    23,    30,    45],
+            [9,   10,    11,    16,    18,    24,    32,    48]
+        ];
+
+        static FONT_SIZE_FACTORS: [i32; 8] = [60, 75, 89, 100, 120, 150, 200, 300];
+
+        let base_size = unsafe {
+            Gecko_nsStyleFont_GetBaseSize(cx.style().get_font().gecko(),
+                                          &*cx.device.pres_context)
+        };
+        let base_size_px = au_to_int_px(base_size as f32);
+        let html_size = *self as usize;
+        let mapping = if cx.quirks_mode == QuirksMode::Quirks {
+            &FONT_SIZE_MAPPING_QUIRKS
+        } else {
+            &FONT_SIZE_MAPPING
+        };
+        if base_size_px >= 9 && base_size_px <= 16 {
+            Au::from_px(mapping[(base_size_px - 9) as usize][html_size])
+        } else {
+            Au(FONT_SIZE_FACTORS[html_size] * base_size / 100)
+        }
+    }
+
+    #[inline]
+    fn from_computed_value(_: &Au) -> Self {
+        unreachable!()
+    }
+}
+
+/// Whether parsing should accept a value's quirks-mode-only form (currently
+/// only `font-size`'s unitless number meaning pixels, see `parse_quirky`)
+/// when the document is in quirks mode. The keyword-to-pixel tables do not
+/// look at this; they follow the document's quirks mode directly.
+#[derive(Clone, Copy, PartialEq)]
+pub enum AllowQuirks {
+    Yes,
+    No,
+}
+
+/// The specified value of `font-size`: `<length> | <percentage> |
+/// <absolute-size> | <relative-size>`, plus the internal system-font
+/// variant used while resolving CSS-wide system font keywords.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "servo", derive(HeapSizeOf))]
+pub enum FontSize {
+    Length(LengthOrPercentage),
+    Keyword(KeywordSize),
+    Smaller,
+    Larger,
+    System(SystemFont),
+}
+
+impl FontSize {
+    /// Maps the legacy HTML `size` attribute (already clamped to `1..=7`,
+    /// with `0` treated as `1`) onto a keyword.
+    pub fn from_html_size(size: u8) -> Self {
+        FontSize::Keyword(match size {
+            0 | 1 => XSmall,
+            2 => Small,
+            3 => Medium,
+            4 => Large,
+            5 => XLarge,
+            6 => XXLarge,
+            _ => XXXLarge,
+        })
+    }
+
+    pub fn system_font(f: SystemFont) -> Self {
+        FontSize::System(f)
+    }
+
+    pub fn get_system(&self) -> Option<SystemFont> {
+        if let FontSize::System(s) = *self {
+            Some(s)
+        } else {
+            None
+        }
+    }
+}
+
+impl ToCss for FontSize {
+    fn to_css<W>(&self, dest: &mut W) -> fmt::Result where W: fmt::Write {
+        match *self {
+            FontSize::Length(ref lop) => lop.to_css(dest),
+            FontSize::Keyword(kw) => kw.to_css(dest),
+            FontSize::Smaller => dest.write_str("smaller"),
+            FontSize::Larger => dest.write_str("larger"),
+            FontSize::System(_) => Ok(()),
+        }
+    }
+}
+
+impl HasViewportPercentage for FontSize {
+    fn has_viewport_percentage(&self) -> bool {
+        match *self {
+            FontSize::Length(ref lop) => lop.has_viewport_percentage(),
+            _ => false
+        }
+    }
+}
+
+impl ToComputedValue for FontSize {
+    type ComputedValue = ComputedFontSize;
+
+    #[inline]
+    fn to_computed_value(&self, context: &Context) -> ComputedFontSize {
+        let compose_keyword = |factor, offset| {
+            context.inherited_style().get_font().clone_font_size().keyword_info
+                   .map(|i| i.compose(factor, offset))
+        };
+        match *self {
+            FontSize::Length(LengthOrPercentage::Length(
+                    NoCalcLength::FontRelative(value))) => {
+                let size = value.to_computed_value(context, /* use inherited */ true);
+                ComputedFontSize::new(size, None)
+            }
+            FontSize::Length(LengthOrPercentage::Length(
+                    NoCalcLength::ServoCharacterWidth(value))) => {
+                let size =
+                    value.to_computed_value(context.inherited_style().get_font().clone_font_size().size);
+                ComputedFontSize::new(size, None)
+            }
+            FontSize::Length(LengthOrPercentage::Length(ref l)) => {
+                ComputedFontSize::new(l.to_computed_value(context), None)
+            }
+            FontSize::Length(LengthOrPercentage::Percentage(Percentage(value))) => {
+                let size = context.inherited_style().get_font().clone_font_size().size
+                                   .scale_by(value);
+                ComputedFontSize::new(size, compose_keyword(value, Au(0)))
+            }
+            FontSize::Length(LengthOrPercentage::Calc(ref calc)) => {
+                let calc = calc.to_computed_value(context);
+                let parent = context.inherited_style().get_font().clone_font_size();
+                let size = calc.length() + parent.size.scale_by(calc.percentage());
+                // The calc expression's absolute part feeds into `offset`
+                // the same way its percentage part feeds into `factor`, so
+                // a later recompute from the keyword (zoom,
+                // `-moz-min-font-size-ratio`) reproduces this size exactly.
+                ComputedFontSize::new(size, compose_keyword(calc.percentage(), calc.length()))
+            }
+            FontSize::Keyword(ref key) => {
+                let size = key.to_computed_value(context);
+                ComputedFontSize::new(size, Some(KeywordInfo::new(*key)))
+            }
+            FontSize::Smaller => {
+                let parent = context.inherited_style().get_font().clone_font_size();
+                let size = parent.size.scale_by(0.85);
+                ComputedFontSize::new(size, compose_keyword(0.85, Au(0)))
+            }
+            FontSize::Larger => {
+                let parent = context.inherited_style().get_font().clone_font_size();
+                let size = parent.size.scale_by(1.2);
+                ComputedFontSize::new(size, compose_keyword(1.2, Au(0)))
+            }
+            FontSize::System(system) => {
+                system_font::cached_system_font(context, system).unwrap().font_size
+            }
+        }
+    }
+
+    #[inline]
+    fn from_computed_value(computed: &ComputedFontSize) -> Self {
+        if let Some(ref info) = computed.keyword_info {
+            FontSize::Keyword(info.kw)
+        } else {
+            FontSize::Length(LengthOrPercentage::Length(
+                    ToComputedValue::from_computed_value(&computed.size)
+            ))
+        }
+    }
+}
+
+/// <length> | <percentage> | <absolute-size> | <relative-size>
+pub fn parse(context: &ParserContext, input: &mut Parser) -> Result<FontSize, ()> {
+    parse_quirky(context, input, AllowQuirks::No)
+}
+
+/// Parses `font-size`, additionally allowing the classic HTML quirk of a
+/// bare unitless non-negative number meaning pixels when both the document
+/// is in quirks mode and the caller passes `AllowQuirks::Yes` (e.g. parsing
+/// the deprecated `size` presentation attribute).
+pub fn parse_quirky(context: &ParserContext,
+                     input: &mut Parser,
+                     allow_quirks: AllowQuirks)
+                     -> Result<FontSize, ()> {
+    use selectors::parser::QuirksMode;
+    if allow_quirks == AllowQuirks::Yes && context.quirks_mode == QuirksMode::Quirks {
+        if let Ok(value) = input.try(|input| input.expect_number()) {
+            if value >= 0. {
+                return Ok(FontSize::Length(NoCalcLength::from_px(value).into()));
+            }
+        }
+    }
+    if let Ok(lop) = input.try(LengthOrPercentage::parse_non_negative) {
+        Ok(FontSize::Length(lop))
+    } else if let Ok(kw) = input.try(KeywordSize::parse) {
+        Ok(FontSize::Keyword(kw))
+    } else {
+        match_ignore_ascii_case! {&*input.expect_ident()?,
+            "smaller" => Ok(FontSize::Smaller),
+            "larger" => Ok(FontSize::Larger),
+            _ => Err(())
+        }
+    }
+}
+
+#[inline]
+pub fn get_initial_value() -> ComputedFontSize {
+    ComputedFontSize::new(Au::from_px(FONT_MEDIUM_PX), Some(KeywordInfo::new(Medium)))
+}
+
+#[inline]
+pub fn get_initial_specified_value() -> FontSize {
+    FontSize::Keyword(Medium)
+}
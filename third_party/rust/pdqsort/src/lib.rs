@@ -39,11 +39,38 @@
 
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "rayon")]
+extern crate rayon_core;
+
 use core::cmp::Ordering::{self, Equal, Greater, Less};
 use core::cmp;
 use core::mem;
+use core::mem::MaybeUninit;
 use core::ptr;
 
+/// When dropped, copies `count` elements from `src` into `dest`.
+///
+/// Expresses "uninitialized scratch that gets written back on panic": if
+/// whatever loop is using this guard panics partway through, the value(s)
+/// temporarily parked at `src` get copied back into their rightful place
+/// at `dest` instead of being silently dropped or duplicated.
+struct CopyOnDrop<T> {
+    src: *const T,
+    dest: *mut T,
+    count: usize,
+}
+
+impl<T> Drop for CopyOnDrop<T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::copy_nonoverlapping(self.src, self.dest, self.count);
+        }
+    }
+}
+
 /// Inserts `v[0]` into pre-sorted sequence `v[1..]` so that whole `v[..]` becomes sorted, and
 /// returns `true` if the sequence was modified.
 ///
@@ -51,29 +78,6 @@ use core::ptr;
 fn insert_head<T, F>(v: &mut [T], compare: &mut F) -> bool
     where F: FnMut(&T, &T) -> Ordering
 {
-    // Holds a value, but never drops it.
-    struct NoDrop<T> {
-        value: Option<T>,
-    }
-
-    impl<T> Drop for NoDrop<T> {
-        fn drop(&mut self) {
-            mem::forget(self.value.take());
-        }
-    }
-
-    // When dropped, copies from `src` into `dest`.
-    struct InsertionHole<T> {
-        src: *mut T,
-        dest: *mut T,
-    }
-
-    impl<T> Drop for InsertionHole<T> {
-        fn drop(&mut self) {
-            unsafe { ptr::copy_nonoverlapping(self.src, self.dest, 1); }
-        }
-    }
-
     if v.len() >= 2 && compare(&v[0], &v[1]) == Greater {
         unsafe {
             // There are three ways to implement insertion here:
@@ -93,7 +97,7 @@ fn insert_head<T, F>(v: &mut [T], compare: &mut F) -> bool
             //    performance than with the 2nd method.
             //
             // All methods were benchmarked, and the 3rd showed best results. So we chose that one.
-            let mut tmp = NoDrop { value: Some(ptr::read(&v[0])) };
+            let mut tmp = MaybeUninit::new(ptr::read(&v[0]));
 
             // Intermediate state of the insertion process is always tracked by `hole`, which
             // serves two purposes:
@@ -105,14 +109,15 @@ fn insert_head<T, F>(v: &mut [T], compare: &mut F) -> bool
             // If `compare` panics at any point during the process, `hole` will get dropped and
             // fill the hole in `v` with `tmp`, thus ensuring that `v` still holds every object it
             // initially held exactly once.
-            let mut hole = InsertionHole {
-                src: tmp.value.as_mut().unwrap(),
+            let mut hole = CopyOnDrop {
+                src: tmp.as_mut_ptr(),
                 dest: &mut v[1],
+                count: 1,
             };
             ptr::copy_nonoverlapping(&v[1], &mut v[0], 1);
 
             for i in 2..v.len() {
-                if compare(tmp.value.as_ref().unwrap(), &v[i]) != Greater {
+                if compare(&*tmp.as_ptr(), &v[i]) != Greater {
                     break;
                 }
                 ptr::copy_nonoverlapping(&v[i], &mut v[i - 1], 1);
@@ -623,6 +628,542 @@ pub fn sort_by<T, F>(v: &mut [T], mut compare: F)
     quicksort(v, &mut compare, None, limit);
 }
 
+/// Reorders `v` so that the element at `index` ends up in the position it
+/// would occupy if `v` were fully sorted, every element before it compares
+/// `<=` it, and every element after it compares `>=` it.
+///
+/// This reuses the same building blocks as `quicksort`: `choose_pivot` picks
+/// a pivot and `partition` splits the slice around it, but instead of
+/// recursing into both halves, only the half containing `index` is visited.
+/// The equal-pivot fast path via `partition_equal` and the `limit` counter
+/// that falls back to `heapsort` once exhausted are unchanged from
+/// `quicksort`, so quickselect keeps the same `O(n)` average, `O(n log n)`
+/// worst-case bound.
+fn quickselect<T, F>(v: &mut [T], index: usize, compare: &mut F, pred: Option<&T>, mut limit: usize)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let max_insertion = if mem::size_of::<T>() <= 2 * mem::size_of::<usize>() {
+        32
+    } else {
+        16
+    };
+
+    let len = v.len();
+
+    if len <= max_insertion {
+        insertion_sort(v, compare);
+        return;
+    }
+
+    if limit == 0 {
+        // Worst-case escape hatch, exactly like quicksort's own: guarantee
+        // linear-time selection stays bounded by falling back to heapsort
+        // for what's left of the window.
+        heapsort(v, compare);
+        return;
+    }
+
+    let mid = choose_pivot(v, compare);
+
+    if let Some(p) = pred {
+        if compare(p, &v[mid]) == Equal {
+            let mid = partition_equal(v, mid, compare);
+            if index >= mid {
+                quickselect(&mut v[mid..], index - mid, compare, pred, limit);
+            }
+            return;
+        }
+    }
+
+    let (mid, _was_partitioned) = partition(v, mid, compare);
+
+    // Imbalanced whenever *either* side is tiny relative to `len`, exactly
+    // like `quicksort`'s own check -- a partition that discards almost
+    // nothing while keeping almost everything is the adversarial pattern
+    // `limit`/`break_patterns` exist to catch, even though only the kept
+    // side is recursed into here.
+    let imbalanced = mid < len / 8 || len - mid - 1 < len / 8;
+
+    match index.cmp(&mid) {
+        Less => {
+            let left = &mut v[..mid];
+            if imbalanced {
+                limit -= 1;
+                break_patterns(left);
+            }
+            quickselect(left, index, compare, pred, limit);
+        }
+        Equal => {}
+        Greater => {
+            let (_left, right) = v.split_at_mut(mid);
+            let (pivot, right) = right.split_at_mut(1);
+            let pivot = &pivot[0];
+            if imbalanced {
+                limit -= 1;
+                break_patterns(right);
+            }
+            quickselect(right, index - mid - 1, compare, Some(pivot), limit);
+        }
+    }
+}
+
+/// Reorders `v` using the ordering defined by `compare` so that the element
+/// at `index` ends up where it would be if `v` were fully sorted. Returns
+/// the three-way split around it: everything before (`<=`), the selected
+/// element itself, and everything after (`>=`).
+///
+/// This is an `O(n)`-average (`O(n log n)` worst-case) partial sort, useful
+/// for things like medians without paying for a full sort.
+///
+/// # Examples
+///
+/// ```
+/// extern crate pdqsort;
+///
+/// let mut v = [-5i32, 4, 1, -3, 2];
+/// let (left, mid, right) = pdqsort::select_nth_unstable_by(&mut v, 2, |a, b| a.cmp(b));
+/// assert!(left.iter().all(|x| x <= mid));
+/// assert!(right.iter().all(|x| x >= mid));
+/// assert_eq!(*mid, 1);
+/// ```
+#[inline]
+pub fn select_nth_unstable_by<T, F>(v: &mut [T], index: usize, mut compare: F)
+    -> (&mut [T], &mut T, &mut [T])
+    where F: FnMut(&T, &T) -> Ordering
+{
+    assert!(index < v.len(), "index out of bounds");
+
+    // Selection has no meaningful behavior on zero-sized types.
+    if mem::size_of::<T>() != 0 {
+        let len = v.len() as u64;
+        let limit = 64 - len.leading_zeros() as usize + 1;
+
+        quickselect(v, index, &mut compare, None, limit);
+    }
+
+    let (left, rest) = v.split_at_mut(index);
+    let (mid, right) = rest.split_at_mut(1);
+    (left, &mut mid[0], right)
+}
+
+/// Reorders `v` so that the element at `index` ends up where it would be if
+/// `v` were fully sorted. Returns the three-way split around it.
+///
+/// # Examples
+///
+/// ```
+/// extern crate pdqsort;
+///
+/// let mut v = [-5i32, 4, 1, -3, 2];
+/// let (left, mid, right) = pdqsort::select_nth_unstable(&mut v, 2);
+/// assert!(left.iter().all(|x| x <= mid));
+/// assert!(right.iter().all(|x| x >= mid));
+/// assert_eq!(*mid, 1);
+/// ```
+#[inline]
+pub fn select_nth_unstable<T>(v: &mut [T], index: usize) -> (&mut [T], &mut T, &mut [T])
+    where T: Ord
+{
+    select_nth_unstable_by(v, index, |a, b| a.cmp(b))
+}
+
+/// Reorders `v` using `f` to extract a key to compare elements by, so that
+/// the element at `index` ends up where it would be if `v` were fully
+/// sorted. Returns the three-way split around it.
+///
+/// # Examples
+///
+/// ```
+/// extern crate pdqsort;
+///
+/// let mut v = [-5i32, 4, 1, -3, 2];
+/// let (left, mid, right) = pdqsort::select_nth_unstable_by_key(&mut v, 2, |k| k.abs());
+/// assert_eq!(*mid, 2);
+/// ```
+#[inline]
+pub fn select_nth_unstable_by_key<T, B, F>(v: &mut [T], index: usize, mut f: F)
+    -> (&mut [T], &mut T, &mut [T])
+    where F: FnMut(&T) -> B,
+          B: Ord
+{
+    select_nth_unstable_by(v, index, |a, b| f(a).cmp(&f(b)))
+}
+
+
+/// Merges the adjacent sorted runs `v[..mid]` and `v[mid..]` back into `v`,
+/// using `buf` as `mem::size_of::<T>() * min(mid, v.len() - mid)` bytes of
+/// scratch space.
+///
+/// # Safety
+///
+/// Both runs must be non-empty, `mid` must be in bounds, `buf` must be
+/// valid for at least `min(mid, v.len() - mid)` writes, and `T` must not be
+/// a zero-sized type.
+#[cfg(feature = "alloc")]
+unsafe fn merge<T, F>(v: &mut [T], mid: usize, buf: *mut T, compare: &mut F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let len = v.len();
+    let v = v.as_mut_ptr();
+    let v_mid = v.offset(mid as isize);
+    let v_end = v.offset(len as isize);
+
+    let left_len = mid;
+    let right_len = len - mid;
+
+    if left_len <= right_len {
+        // The left run is shorter (or equal): copy it into `buf`, then trace
+        // it and the right run (still in `v`) forwards, always copying the
+        // lesser of the two next unconsumed elements into the front of `v`.
+        ptr::copy_nonoverlapping(v, buf, left_len);
+        let mut hole = CopyOnDrop { src: buf, dest: v, count: left_len };
+
+        let mut l = buf;
+        let mut r = v_mid;
+        let mut out = v;
+
+        while hole.count > 0 && r < v_end {
+            if compare(&*r, &*l) == Less {
+                ptr::copy_nonoverlapping(r, out, 1);
+                r = r.offset(1);
+            } else {
+                ptr::copy_nonoverlapping(l, out, 1);
+                l = l.offset(1);
+                hole.src = l;
+                hole.count -= 1;
+            }
+            out = out.offset(1);
+            hole.dest = out;
+        }
+        // If the right run ran out first, `hole` copies whatever's left of
+        // the left run (still in `buf`) into the tail of `v` when dropped.
+        // If the left run ran out first, `hole.count` is already zero and
+        // dropping it is a no-op.
+    } else {
+        // The right run is shorter: copy it into `buf`, then trace it and
+        // the left run (still in `v`) backwards, always copying the
+        // greater of the two next unconsumed elements into the back of `v`.
+        ptr::copy_nonoverlapping(v_mid, buf, right_len);
+        let mut hole = CopyOnDrop { src: buf, dest: v, count: right_len };
+
+        let mut l = v_mid;
+        let mut r = buf.offset(right_len as isize);
+        let mut out = v_end;
+
+        while hole.count > 0 && l > v {
+            if compare(&*r.offset(-1), &*l.offset(-1)) == Less {
+                l = l.offset(-1);
+                out = out.offset(-1);
+                ptr::copy_nonoverlapping(l, out, 1);
+            } else {
+                r = r.offset(-1);
+                out = out.offset(-1);
+                ptr::copy_nonoverlapping(r, out, 1);
+                hole.count -= 1;
+            }
+        }
+        // Symmetric to the case above: whatever's left of the right run is
+        // always the front `hole.count` elements of `buf`, and the only
+        // unwritten region of `v` left once the left run is exhausted is
+        // always its front `hole.count` slots, so `hole.src`/`hole.dest`
+        // never need to move -- only `hole.count` does.
+    }
+}
+
+/// A run of non-decreasing elements found while scanning `v`, tracked by
+/// its starting index and length.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy)]
+struct Run {
+    start: usize,
+    len: usize,
+}
+
+/// Decides whether the last two runs on the stack should be merged with
+/// their predecessor to keep the stack's invariant (each run roughly no
+/// smaller than the sum of the two runs after it), bounding total merge
+/// work the way timsort's run-collapsing rule does. Returns the index of
+/// the earlier of the pair to merge, if any.
+#[cfg(feature = "alloc")]
+fn collapse(runs: &[Run]) -> Option<usize> {
+    let n = runs.len();
+    if n >= 2 && (runs[n - 1].start == 0
+        || runs[n - 2].len <= runs[n - 1].len
+        || (n >= 3 && runs[n - 3].len <= runs[n - 2].len + runs[n - 1].len)
+        || (n >= 4 && runs[n - 4].len <= runs[n - 3].len + runs[n - 2].len))
+    {
+        if n >= 3 && runs[n - 3].len < runs[n - 1].len {
+            Some(n - 3)
+        } else {
+            Some(n - 2)
+        }
+    } else {
+        None
+    }
+}
+
+/// Sorts `v` using merge sort, preserving the relative order of equal
+/// elements (unlike `quicksort`/`heapsort`, which may reorder them).
+///
+/// Scans `v` from right to left building ascending runs -- extending a
+/// descending prefix by reversing it in place, and stretching runs shorter
+/// than `MIN_RUN` out with `insert_head`, same as `insertion_sort` uses --
+/// then merges adjacent runs on a stack with `merge`, using a scratch
+/// buffer of at most `v.len() / 2` elements allocated once up front.
+#[cfg(feature = "alloc")]
+fn merge_sort<T, F>(v: &mut [T], mut compare: F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    use alloc::vec::Vec;
+
+    // Slices up to this length are sorted with plain insertion sort, same
+    // as `quicksort`'s own threshold.
+    const MAX_INSERTION: usize = 20;
+    // Runs shorter than this are extended with `insert_head` so the merge
+    // stack below isn't stuck doing lots of tiny merges.
+    const MIN_RUN: usize = 10;
+
+    let len = v.len();
+
+    if len <= MAX_INSERTION {
+        insertion_sort(v, &mut compare);
+        return;
+    }
+
+    // Allocated once, sized for the largest scratch space any single merge
+    // below could need (never more than half of `v`).
+    let mut buf: Vec<T> = Vec::with_capacity(len / 2);
+    let buf_ptr = buf.as_mut_ptr();
+
+    let mut runs = Vec::new();
+    let mut end = len;
+    while end > 0 {
+        let mut start = end - 1;
+
+        if start > 0 {
+            start -= 1;
+            unsafe {
+                if compare(v.get_unchecked(start + 1), v.get_unchecked(start)) == Less {
+                    while start > 0 && compare(v.get_unchecked(start), v.get_unchecked(start - 1)) == Less {
+                        start -= 1;
+                    }
+                    v[start..end].reverse();
+                } else {
+                    while start > 0 && compare(v.get_unchecked(start), v.get_unchecked(start - 1)) != Less {
+                        start -= 1;
+                    }
+                }
+            }
+        }
+
+        while start > 0 && end - start < MIN_RUN {
+            start -= 1;
+            insert_head(&mut v[start..end], &mut compare);
+        }
+
+        runs.push(Run { start: start, len: end - start });
+        end = start;
+
+        while let Some(r) = collapse(&runs) {
+            let left = runs[r + 1];
+            let right = runs[r];
+            unsafe {
+                merge(&mut v[left.start..right.start + right.len], left.len, buf_ptr, &mut compare);
+            }
+            runs[r] = Run { start: left.start, len: left.len + right.len };
+            runs.remove(r + 1);
+        }
+    }
+
+    debug_assert!(runs.len() == 1 && runs[0].start == 0 && runs[0].len == len);
+}
+
+/// Sorts a slice, preserving the relative order of equal elements.
+///
+/// This sort allocates a scratch buffer of at most `v.len() / 2` elements,
+/// unlike `sort`, which is allocation-free. Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// extern crate pdqsort;
+///
+/// let mut v = [-5i32, 4, 1, -3, 2];
+/// pdqsort::sort_stable(&mut v);
+/// assert!(v == [-5, -3, 1, 2, 4]);
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn sort_stable<T>(v: &mut [T])
+    where T: Ord
+{
+    sort_stable_by(v, |a, b| a.cmp(b));
+}
+
+/// Sorts a slice using `f` to extract a key to compare elements by,
+/// preserving the relative order of equal elements. Requires the `alloc`
+/// feature.
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn sort_stable_by_key<T, B, F>(v: &mut [T], mut f: F)
+    where F: FnMut(&T) -> B,
+          B: Ord
+{
+    sort_stable_by(v, |a, b| f(a).cmp(&f(b)))
+}
+
+/// Sorts a slice using `compare` to compare elements, preserving the
+/// relative order of elements considered equal. Requires the `alloc`
+/// feature.
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn sort_stable_by<T, F>(v: &mut [T], compare: F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    // Sorting has no meaningful behavior on zero-sized types.
+    if mem::size_of::<T>() == 0 {
+        return;
+    }
+
+    merge_sort(v, compare);
+}
+
+/// Below this slice length, `par_quicksort` stops forking and recurses
+/// sequentially. Joining has its own overhead, and slices this small finish
+/// faster than the cost of handing them to another thread.
+#[cfg(feature = "rayon")]
+const PARALLEL_THRESHOLD: usize = 4096;
+
+/// Like `quicksort`, but runs the two halves produced by `partition` on the
+/// rayon thread pool via `rayon_core::join` once `v` is bigger than
+/// `PARALLEL_THRESHOLD`. Below the threshold it recurses sequentially, just
+/// like `quicksort`.
+///
+/// `compare` is shared (not exclusively borrowed) so that both halves can
+/// call it concurrently; `T: Send + Sync` is required because `left`,
+/// `right`, and `pred` may all be handed to another thread.
+#[cfg(feature = "rayon")]
+fn par_quicksort<T, F>(v: &mut [T], compare: &F, pred: Option<&T>, mut limit: usize)
+    where T: Send + Sync, F: Fn(&T, &T) -> Ordering + Sync
+{
+    // Below this length, forking further isn't worth it: fall back to the
+    // sequential algorithm entirely, reusing it verbatim via a `FnMut`
+    // wrapper around our shared `compare`.
+    let max_insertion = if mem::size_of::<T>() <= 2 * mem::size_of::<usize>() {
+        32
+    } else {
+        16
+    };
+
+    let len = v.len();
+    let mut cmp = |a: &T, b: &T| compare(a, b);
+
+    if len <= max_insertion {
+        insertion_sort(v, &mut cmp);
+        return;
+    }
+
+    if limit == 0 {
+        heapsort(v, &mut cmp);
+        return;
+    }
+
+    let mid = choose_pivot(v, &mut cmp);
+
+    if let Some(p) = pred {
+        if compare(p, &v[mid]) == Equal {
+            let mid = partition_equal(v, mid, &mut cmp);
+            par_quicksort(&mut v[mid..], compare, pred, limit);
+            return;
+        }
+    }
+
+    let (mid, was_partitioned) = partition(v, mid, &mut cmp);
+    let (left, right) = v.split_at_mut(mid);
+    let (pivot, right) = right.split_at_mut(1);
+    let pivot = &pivot[0];
+
+    if left.len() < len / 8 || right.len() < len / 8 {
+        limit -= 1;
+        break_patterns(left);
+        break_patterns(right);
+    } else {
+        if was_partitioned && partial_insertion_sort(left, &mut cmp)
+                           && partial_insertion_sort(right, &mut cmp) {
+            return;
+        }
+    }
+
+    if len > PARALLEL_THRESHOLD {
+        rayon_core::join(
+            || par_quicksort(left, compare, pred, limit),
+            || par_quicksort(right, compare, Some(pivot), limit),
+        );
+    } else {
+        par_quicksort(left, compare, pred, limit);
+        par_quicksort(right, compare, Some(pivot), limit);
+    }
+}
+
+/// Sorts a slice, forking onto the rayon thread pool for large enough
+/// sub-slices.
+///
+/// This is the parallel counterpart to `sort`: same in-place, unstable,
+/// `O(n log n)` worst-case algorithm, but big slices are divided and
+/// conquered across threads via `rayon_core::join`.
+///
+/// Requires the `rayon` feature.
+#[cfg(feature = "rayon")]
+#[inline]
+pub fn par_sort<T>(v: &mut [T])
+    where T: Ord + Send + Sync
+{
+    par_sort_by(v, |a, b| a.cmp(b));
+}
+
+/// Sorts a slice using `f` to extract a key to compare elements by, forking
+/// onto the rayon thread pool for large enough sub-slices.
+///
+/// Requires the `rayon` feature.
+#[cfg(feature = "rayon")]
+#[inline]
+pub fn par_sort_by_key<T, B, F>(v: &mut [T], f: F)
+    where F: Fn(&T) -> B + Sync,
+          B: Ord,
+          T: Send + Sync
+{
+    par_sort_by(v, |a, b| f(a).cmp(&f(b)))
+}
+
+/// Sorts a slice using `compare` to compare elements, forking onto the rayon
+/// thread pool for large enough sub-slices.
+///
+/// `compare` is `Fn` rather than `FnMut` (unlike `sort_by`'s `compare`)
+/// since it may be called concurrently from multiple threads.
+///
+/// Requires the `rayon` feature.
+#[cfg(feature = "rayon")]
+#[inline]
+pub fn par_sort_by<T, F>(v: &mut [T], compare: F)
+    where F: Fn(&T, &T) -> Ordering + Sync,
+          T: Send + Sync
+{
+    // Sorting has no meaningful behavior on zero-sized types.
+    if mem::size_of::<T>() == 0 {
+        return;
+    }
+
+    let mut cmp = |a: &T, b: &T| compare(a, b);
+    if is_presorted(v, &mut cmp) {
+        return;
+    }
+
+    let len = v.len() as u64;
+    let limit = 64 - len.leading_zeros() as usize + 1;
+
+    par_quicksort(v, &compare, None, limit);
+}
+
 #[cfg(test)]
 mod tests {
     extern crate std;
@@ -711,4 +1252,90 @@ mod tests {
             }
         });
     }
+
+    #[test]
+    fn test_select_nth_unstable() {
+        let mut rng = thread_rng();
+        for n in 1..16 {
+            for l in 0..16 {
+                let v = rng.gen_iter::<u64>()
+                    .map(|x| x % (1 << l))
+                    .take((1 << n))
+                    .collect::<Vec<_>>();
+
+                for _ in 0..4 {
+                    let index = rng.gen_range(0, v.len());
+                    let mut v = v.clone();
+
+                    let (left, mid, right) = super::select_nth_unstable(&mut v, index);
+                    assert!(left.iter().all(|x| x <= mid));
+                    assert!(right.iter().all(|x| x >= mid));
+
+                    let mut sorted = v.clone();
+                    sorted.sort();
+                    assert_eq!(*mid, sorted[index]);
+                }
+            }
+        }
+
+        let mut v = [0xDEADBEEFu64];
+        let (left, mid, right) = super::select_nth_unstable(&mut v, 0);
+        assert!(left.is_empty() && right.is_empty());
+        assert_eq!(*mid, 0xDEADBEEF);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_sort_stable() {
+        let mut rng = thread_rng();
+        for n in 0..16 {
+            for l in 0..16 {
+                let v = rng.gen_iter::<u64>()
+                    .map(|x| x % (1 << l))
+                    .take((1 << n))
+                    .collect::<Vec<_>>();
+
+                // Pair each value with its original index so stability can
+                // be checked: after sorting by value alone, equal values
+                // must still appear in their original relative order.
+                let mut tagged = v.iter().cloned().enumerate()
+                    .map(|(i, x)| (x, i))
+                    .collect::<Vec<_>>();
+
+                super::sort_stable_by_key(&mut tagged, |&(x, _)| x);
+
+                assert!(tagged.windows(2).all(|w| w[0].0 <= w[1].0));
+                assert!(tagged.windows(2).all(|w| w[0].0 != w[1].0 || w[0].1 < w[1].1));
+            }
+        }
+
+        let mut v = [0xDEADBEEFu64];
+        super::sort_stable(&mut v);
+        assert!(v == [0xDEADBEEF]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_pdqsort() {
+        let mut rng = thread_rng();
+        for n in 0..16 {
+            for l in 0..16 {
+                let mut v = rng.gen_iter::<u64>()
+                    .map(|x| x % (1 << l))
+                    .take((1 << n))
+                    .collect::<Vec<_>>();
+                let mut v1 = v.clone();
+
+                super::par_sort(&mut v);
+                assert!(v.windows(2).all(|w| w[0] <= w[1]));
+
+                v1.sort_by(|a, b| a.cmp(b));
+                assert!(v1.windows(2).all(|w| w[0] <= w[1]));
+            }
+        }
+
+        let mut v = [0xDEADBEEFu64];
+        super::par_sort(&mut v);
+        assert!(v == [0xDEADBEEF]);
+    }
 }